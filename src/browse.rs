@@ -0,0 +1,230 @@
+use std::{collections::HashMap, io, time::Duration};
+
+use anyhow::Result;
+use crossterm::{
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    backend::{Backend, CrosstermBackend},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Row, Table, TableState},
+    Frame, Terminal,
+};
+
+use crate::client::{Collection, List, QueryItem, Wishlist};
+
+/// Which collection is currently being browsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Source {
+    Collection,
+    Wishlist,
+}
+
+impl Source {
+    fn toggled(self) -> Self {
+        match self {
+            Source::Collection => Source::Wishlist,
+            Source::Wishlist => Source::Collection,
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Source::Collection => "Collection",
+            Source::Wishlist => "Wishlist",
+        }
+    }
+}
+
+struct App {
+    fan_id: u32,
+    identity: String,
+    ttl: Duration,
+    source: Source,
+    items: Vec<QueryItem>,
+    filter: String,
+    // Keyed by album_id so the full item survives switching between the
+    // collection and wishlist views, which reload `items` from scratch.
+    selected: HashMap<u32, QueryItem>,
+    table_state: TableState,
+}
+
+impl App {
+    async fn new(fan_id: u32, identity: String, ttl: Duration) -> Result<Self> {
+        let mut table_state = TableState::default();
+        table_state.select(Some(0));
+
+        let mut app = Self {
+            fan_id,
+            identity,
+            ttl,
+            source: Source::Collection,
+            items: vec![],
+            filter: String::new(),
+            selected: HashMap::new(),
+            table_state,
+        };
+        app.reload().await?;
+
+        Ok(app)
+    }
+
+    async fn reload(&mut self) -> Result<()> {
+        self.items = match self.source {
+            Source::Collection => {
+                Collection::list(self.fan_id, &self.identity, self.ttl, false).await?
+            }
+            Source::Wishlist => Wishlist::list(self.fan_id, &self.identity, self.ttl, false).await?,
+        };
+        self.table_state.select(Some(0));
+
+        Ok(())
+    }
+
+    fn filtered(&self) -> Vec<&QueryItem> {
+        let needle = self.filter.to_lowercase();
+        self.items
+            .iter()
+            .filter(|item| {
+                needle.is_empty()
+                    || item.band_name.to_lowercase().contains(&needle)
+                    || item.album_title.to_lowercase().contains(&needle)
+            })
+            .collect()
+    }
+}
+
+/// Run the interactive `camper browse` TUI, returning the full collection
+/// metadata of the items the user selected for download. The result is empty
+/// if the user confirmed an empty selection or quit with Esc, which discards
+/// any selection.
+pub async fn run(fan_id: u32, identity: &str, ttl: Duration) -> Result<Vec<QueryItem>> {
+    let mut app = App::new(fan_id, identity.to_owned(), ttl).await?;
+
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    let result = event_loop(&mut terminal, &mut app).await;
+
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
+    terminal.show_cursor()?;
+
+    result
+}
+
+async fn event_loop<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<Vec<QueryItem>> {
+    loop {
+        terminal.draw(|f| draw(f, app))?;
+
+        if let Event::Key(key) = event::read()? {
+            match key.code {
+                // Quit without downloading anything.
+                KeyCode::Esc => return Ok(vec![]),
+                // Enqueue the current selection into the download pipeline.
+                KeyCode::Enter => return Ok(app.selected.values().cloned().collect()),
+                // Switch between browsing the collection and the wishlist.
+                KeyCode::Tab => {
+                    app.source = app.source.toggled();
+                    app.reload().await?;
+                }
+                // Toggle the highlighted row in or out of the selection.
+                KeyCode::Char(' ') => {
+                    // Clone the highlighted item out first: `app.filtered()`
+                    // immutably borrows `app`, which would otherwise still
+                    // be live when `app.selected` is mutated below.
+                    let current = app
+                        .table_state
+                        .selected()
+                        .and_then(|index| app.filtered().get(index).copied())
+                        .cloned();
+
+                    if let Some(item) = current {
+                        if app.selected.remove(&item.album_id).is_none() {
+                            app.selected.insert(item.album_id, item);
+                        }
+                    }
+                }
+                KeyCode::Down => move_selection(app, 1),
+                KeyCode::Up => move_selection(app, -1),
+                KeyCode::Backspace => {
+                    app.filter.pop();
+                    app.table_state.select(Some(0));
+                }
+                // Everything else typed narrows the filter.
+                KeyCode::Char(c) => {
+                    app.filter.push(c);
+                    app.table_state.select(Some(0));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn move_selection(app: &mut App, delta: isize) {
+    let len = app.filtered().len();
+    if len == 0 {
+        return;
+    }
+
+    let current = app.table_state.selected().unwrap_or(0) as isize;
+    let next = (current + delta).clamp(0, len as isize - 1);
+    app.table_state.select(Some(next as usize));
+}
+
+fn draw<B: Backend>(f: &mut Frame<B>, app: &App) {
+    let chunks = Layout::default()
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(f.size());
+
+    let header = Block::default().borders(Borders::ALL).title(format!(
+        "{} — {} selected — filter: {}",
+        app.source.title(),
+        app.selected.len(),
+        app.filter
+    ));
+    f.render_widget(header, chunks[0]);
+
+    let rows = app.filtered().into_iter().map(|item| {
+        let marker = if app.selected.contains_key(&item.album_id) {
+            "[x]"
+        } else {
+            "[ ]"
+        };
+
+        Row::new(vec![
+            Cell::from(marker),
+            Cell::from(item.album_id.to_string()),
+            Cell::from(item.band_name.clone()),
+            Cell::from(item.album_title.clone()),
+        ])
+    });
+
+    let table = Table::new(rows)
+        .header(
+            Row::new(vec!["", "Album ID", "Band", "Album Title"])
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+        )
+        .widths(&[
+            Constraint::Length(4),
+            Constraint::Length(10),
+            Constraint::Percentage(30),
+            Constraint::Percentage(60),
+        ])
+        .highlight_style(Style::default().bg(Color::DarkGray));
+
+    let mut table_state = app.table_state.clone();
+    f.render_stateful_widget(table, chunks[1], &mut table_state);
+}
@@ -0,0 +1,108 @@
+use std::{fs, path::PathBuf, time::Duration};
+
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use directories::UserDirs;
+use serde::{Deserialize, Serialize};
+
+use super::query::QueryItem;
+
+/// A cached listing for a single `(fan_id, collection_name)` pair, along
+/// with the time it was fetched so staleness can be determined later.
+#[derive(Debug, Deserialize, Serialize)]
+struct CacheEntry {
+    fetched_at: chrono::DateTime<Utc>,
+    items: Vec<QueryItem>,
+}
+
+/// An on-disk cache of collection/wishlist listings, keyed by
+/// `(fan_id, collection_name)` and stored under `~/.camper/cache/`. This
+/// avoids re-walking Bandcamp's paginated API on every `list`/`sync`
+/// invocation when the cached listing is still fresh.
+pub struct Cache;
+
+impl Cache {
+    const CACHE_DIR: &'static str = "cache";
+
+    /// Return the cached items for `(fan_id, collection_name)` if a cache
+    /// entry exists and is younger than `ttl`, otherwise `None`. A cache
+    /// file that exists but cannot be parsed is an error.
+    pub fn get(fan_id: u32, collection_name: &str, ttl: Duration) -> Result<Option<Vec<QueryItem>>> {
+        let path = Self::path(fan_id, collection_name)?;
+        let entry: CacheEntry = match fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text)
+                .with_context(|| format!("unable to parse cache file '{}'", path.display()))?,
+            _ => return Ok(None),
+        };
+
+        match Self::is_fresh(entry.fetched_at, ttl) {
+            true => Ok(Some(entry.items)),
+            false => Ok(None),
+        }
+    }
+
+    /// Returns `true` if `fetched_at` is recent enough to still be within
+    /// `ttl` of now.
+    fn is_fresh(fetched_at: chrono::DateTime<Utc>, ttl: Duration) -> bool {
+        match Utc::now().signed_duration_since(fetched_at).to_std() {
+            Ok(age) => age < ttl,
+            Err(_) => false,
+        }
+    }
+
+    /// Persist `items` as the current cached listing for
+    /// `(fan_id, collection_name)`.
+    pub fn put(fan_id: u32, collection_name: &str, items: &[QueryItem]) -> Result<()> {
+        let path = Self::path(fan_id, collection_name)?;
+        let entry = CacheEntry {
+            fetched_at: Utc::now(),
+            items: items.to_vec(),
+        };
+
+        fs::write(&path, serde_json::to_string(&entry)?)
+            .with_context(|| format!("unable to write cache file '{}'", path.display()))?;
+
+        Ok(())
+    }
+
+    /// Returns the path to the cache file for `(fan_id, collection_name)`,
+    /// ensuring the cache directory exists in the process.
+    fn path(fan_id: u32, collection_name: &str) -> Result<PathBuf> {
+        let home = match UserDirs::new() {
+            Some(user_dirs) => user_dirs.home_dir().to_owned(),
+            None => bail!("unable to determine user's home directory"),
+        };
+
+        let dir = home.join(".camper").join(Self::CACHE_DIR);
+        if !dir.exists() {
+            fs::create_dir_all(&dir)
+                .with_context(|| format!("unable to create cache directory '{}'", dir.display()))?;
+        }
+
+        Ok(dir.join(format!("{}_{}.json", fan_id, collection_name)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration as ChronoDuration;
+
+    use super::*;
+
+    #[test]
+    fn an_entry_fetched_just_now_is_fresh() {
+        assert!(Cache::is_fresh(Utc::now(), Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn an_entry_older_than_the_ttl_is_stale() {
+        let fetched_at = Utc::now() - ChronoDuration::seconds(120);
+        assert!(!Cache::is_fresh(fetched_at, Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn an_entry_just_under_the_ttl_is_still_fresh() {
+        let fetched_at = Utc::now() - ChronoDuration::seconds(59);
+        assert!(Cache::is_fresh(fetched_at, Duration::from_secs(60)));
+    }
+}
@@ -0,0 +1,96 @@
+use anyhow::{Context, Result};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::format::Format;
+
+// ---------------------------------------------------------------------------
+// Request & Response Data
+
+/// The required payload for requesting a direct download link for a single
+/// album in a particular audio format.
+#[derive(Debug, Serialize)]
+struct DownloadRequestData {
+    fan_id: u32,
+    album_id: u32,
+    encoding_name: Format,
+}
+
+/// The data returned from the API call to request a download link. If
+/// Bandcamp does not offer `encoding_name` for the requested release,
+/// `download_url` will be `None`.
+#[derive(Debug, Deserialize)]
+struct DownloadResponseData {
+    download_url: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Download URL Lookup
+
+/// Ask Bandcamp for a direct download URL for `album_id` in the given
+/// `format`, authenticating as `fan_id` via the `identity` cookie. Returns
+/// `None` if the release does not offer `format`, so that callers can fall
+/// back to the next format in the chain.
+pub async fn download_url(
+    fan_id: u32,
+    identity: &str,
+    album_id: u32,
+    format: Format,
+) -> Result<Option<String>> {
+    let response = Client::new()
+        .post("https://bandcamp.com/api/fancollection/1/download_url")
+        .header("Cookie", format!("identity={}", identity))
+        .json(&DownloadRequestData {
+            fan_id,
+            album_id,
+            encoding_name: format,
+        })
+        .send()
+        .await
+        .context("failed to request download URL from Bandcamp")?
+        .json::<DownloadResponseData>()
+        .await
+        .context("failed to parse Bandcamp download response")?;
+
+    Ok(response.download_url)
+}
+
+// ---------------------------------------------------------------------------
+// Track Listing
+
+/// The required payload for requesting the per-track metadata of an album.
+#[derive(Debug, Serialize)]
+struct TralbumDetailsRequestData {
+    album_id: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct TralbumDetailsResponseData {
+    tracks: Vec<TrackInfo>,
+}
+
+/// The metadata Bandcamp has on file for a single track within an album,
+/// used to tag downloaded files once extracted.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TrackInfo {
+    pub track_number: u32,
+    pub title: String,
+}
+
+/// Fetch the track listing for `album_id`, in track order, so that
+/// downloaded files can be tagged with the correct track number and title.
+pub async fn track_listing(album_id: u32) -> Result<Vec<TrackInfo>> {
+    let mut response = Client::new()
+        .post("https://bandcamp.com/api/mobile/25/tralbum_details")
+        .json(&TralbumDetailsRequestData { album_id })
+        .send()
+        .await
+        .context("failed to request track listing from Bandcamp")?
+        .json::<TralbumDetailsResponseData>()
+        .await
+        .context("failed to parse Bandcamp track listing response")?;
+
+    response.tracks.sort_by_key(|track| track.track_number);
+
+    Ok(response.tracks)
+}
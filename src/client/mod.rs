@@ -1,11 +1,19 @@
+use std::time::Duration;
+
 use anyhow::Result;
 use async_trait::async_trait;
 use indicatif::{ProgressBar, ProgressStyle};
 
-use self::query::{utc_now_token, QueryBuilder, QueryItem};
+pub use self::query::QueryItem;
+use self::{cache::Cache, query::{utc_now_token, QueryBuilder}};
 
+pub mod download;
+mod cache;
 mod query;
 
+/// The default staleness interval for cached collection/wishlist listings.
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+
 // ---------------------------------------------------------------------------
 // Traits
 
@@ -13,7 +21,17 @@ mod query;
 pub trait List {
     const COLLECTION_NAME: &'static str;
 
-    async fn list(fan_id: u32, identity: &str) -> Result<Vec<QueryItem>> {
+    /// Fetch this collection's items, returning the cached listing if it's
+    /// younger than `ttl` and `refresh` wasn't requested, otherwise walking
+    /// Bandcamp's paginated API and refreshing the cache.
+    async fn list(fan_id: u32, identity: &str, ttl: Duration, refresh: bool) -> Result<Vec<QueryItem>> {
+        if !refresh {
+            if let Some(items) = Cache::get(fan_id, Self::COLLECTION_NAME, ttl)? {
+                log::info!("using cached {} listing", Self::COLLECTION_NAME);
+                return Ok(items);
+            }
+        }
+
         let mut items = vec![];
 
         // Create a progress spinner to indicate to the user that something is indeed
@@ -53,6 +71,8 @@ pub trait List {
             }
         }
 
+        Cache::put(fan_id, Self::COLLECTION_NAME, &items)?;
+
         Ok(items)
     }
 }
@@ -42,7 +42,7 @@ pub struct QueryResponseData {
 
 /// A singular item contained within the collection or wishlist; usually an
 /// album, but sometimes tracks.
-#[derive(Debug, Clone, PartialEq, PartialOrd, Deserialize)]
+#[derive(Debug, Clone, PartialEq, PartialOrd, Deserialize, Serialize)]
 pub struct QueryItem {
     #[serde(deserialize_with = "deserialize_rfc2822_datetime")]
     pub added: DateTime<Utc>,
@@ -3,6 +3,7 @@ use std::{
     fs,
     io::Write as _,
     path::PathBuf,
+    time::Duration,
 };
 
 use anyhow::{bail, Context, Result};
@@ -17,6 +18,10 @@ pub struct Config {
     pub identity: Option<String>,
     pub library: Option<PathBuf>,
     pub format: Option<Format>,
+    pub tag_files: Option<bool>,
+    /// How long, in seconds, a cached collection/wishlist listing remains
+    /// fresh before `list`/`sync` will re-fetch it from Bandcamp.
+    pub cache_ttl: Option<u64>,
 }
 
 impl Config {
@@ -29,9 +34,20 @@ impl Config {
             identity: Some(identity),
             library: Some(library),
             format: Some(format),
+            tag_files: None,
+            cache_ttl: None,
         }
     }
 
+    /// Returns the configured cache TTL, falling back to
+    /// [`client::DEFAULT_CACHE_TTL`](crate::client::DEFAULT_CACHE_TTL) if
+    /// one hasn't been configured.
+    pub fn cache_ttl(&self) -> Duration {
+        self.cache_ttl
+            .map(Duration::from_secs)
+            .unwrap_or(crate::client::DEFAULT_CACHE_TTL)
+    }
+
     pub fn load() -> Result<Self> {
         let path = Self::config_file_path()?;
         let config = match fs::read_to_string(&path) {
@@ -118,6 +134,12 @@ impl Display for Config {
         if let Some(format) = self.format {
             s.push_str(&format!("format:   {}\n", format.to_string()));
         }
+        if let Some(tag_files) = self.tag_files {
+            s.push_str(&format!("tag_files: {}\n", tag_files));
+        }
+        if let Some(cache_ttl) = self.cache_ttl {
+            s.push_str(&format!("cache_ttl: {}s\n", cache_ttl));
+        }
 
         write!(f, "{}", s)
     }
@@ -0,0 +1,264 @@
+use std::{fs, path::Path, sync::Arc};
+
+use anyhow::{bail, ensure, Context, Result};
+use chrono::{DateTime, Utc};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use tokio::{fs::File, io::AsyncWriteExt, sync::Semaphore};
+
+use crate::{
+    client::download::{download_url, track_listing},
+    format::Format,
+    tag,
+};
+
+/// Default number of albums to download concurrently.
+pub const DEFAULT_JOBS: usize = 4;
+
+/// The pieces of collection metadata needed to extract, tag, and name a
+/// downloaded album. `added` falls back to the current time when the
+/// caller doesn't have the album's actual collection metadata on hand (for
+/// example, a bare `camper download <album_id>` outside of a sync).
+#[derive(Debug, Clone)]
+pub struct AlbumMeta {
+    pub band_name: String,
+    pub album_title: String,
+    pub added: DateTime<Utc>,
+}
+
+impl AlbumMeta {
+    /// A best-effort fallback for when an album is requested by ID alone,
+    /// without having first listed the collection it belongs to.
+    pub fn unknown(album_id: u32) -> Self {
+        Self {
+            band_name: "Unknown Band".to_owned(),
+            album_title: format!("Album {}", album_id),
+            added: Utc::now(),
+        }
+    }
+}
+
+/// Returns an error if `jobs` is `0`, which would otherwise build a
+/// `Semaphore` that never hands out a permit and leave every download
+/// blocked forever.
+pub fn validate_jobs(jobs: usize) -> Result<()> {
+    ensure!(jobs > 0, "--jobs must be at least 1");
+
+    Ok(())
+}
+
+/// Download `album_ids` into `library`, running up to `jobs` downloads
+/// concurrently and rendering one progress bar per active download under a
+/// shared `MultiProgress`.
+pub async fn download_albums(
+    fan_id: u32,
+    identity: &str,
+    album_ids: Vec<u32>,
+    formats: &[Format],
+    library: &Path,
+    jobs: usize,
+    tag_files: bool,
+) -> Result<()> {
+    validate_jobs(jobs)?;
+
+    let semaphore = Arc::new(Semaphore::new(jobs));
+    let multi_progress = Arc::new(MultiProgress::new());
+    let identity = Arc::new(identity.to_owned());
+    let formats = Arc::new(formats.to_vec());
+    let library = Arc::new(library.to_owned());
+
+    let tasks = album_ids.into_iter().map(|album_id| {
+        let semaphore = Arc::clone(&semaphore);
+        let multi_progress = Arc::clone(&multi_progress);
+        let identity = Arc::clone(&identity);
+        let formats = Arc::clone(&formats);
+        let library = Arc::clone(&library);
+
+        tokio::spawn(async move {
+            // Hold a permit for the duration of the download, bounding how
+            // many albums are in flight at any one time.
+            let _permit = semaphore.acquire().await.unwrap();
+
+            download_album(
+                fan_id,
+                &identity,
+                album_id,
+                &AlbumMeta::unknown(album_id),
+                &formats,
+                &library,
+                tag_files,
+                &multi_progress,
+            )
+            .await
+        })
+    });
+
+    for task in tasks {
+        task.await??;
+    }
+
+    Ok(())
+}
+
+/// Download `album_id` into `library`, trying each format in `formats` (in
+/// priority order) until Bandcamp offers one that actually exists for this
+/// release, then extract the archive and (optionally) tag the resulting
+/// files. Progress is rendered under `multi_progress`. Returns the format
+/// that was actually downloaded, which may be a fallback rather than the
+/// first entry in `formats`.
+#[allow(clippy::too_many_arguments)]
+pub async fn download_album(
+    fan_id: u32,
+    identity: &str,
+    album_id: u32,
+    meta: &AlbumMeta,
+    formats: &[Format],
+    library: &Path,
+    tag_files: bool,
+    multi_progress: &MultiProgress,
+) -> Result<Format> {
+    for &format in formats {
+        let url = match download_url(fan_id, identity, album_id, format).await? {
+            Some(url) => url,
+            None => continue,
+        };
+
+        log::info!("album {}: downloading as {}", album_id, format);
+        let archive_path = stream_to_library(&url, album_id, library, multi_progress).await?;
+        extract_and_tag(&archive_path, library, album_id, meta, tag_files).await?;
+
+        return Ok(format);
+    }
+
+    bail!(
+        "album {} is not available in any of the requested formats: {:?}",
+        album_id,
+        formats
+    );
+}
+
+/// Stream the archive at `url` into `library`, naming the file after the
+/// final path segment of the download URL and reporting progress as bytes
+/// downloaded / total under `multi_progress`. Returns the path of the
+/// downloaded archive.
+async fn stream_to_library(
+    url: &str,
+    album_id: u32,
+    library: &Path,
+    multi_progress: &MultiProgress,
+) -> Result<std::path::PathBuf> {
+    let mut response = reqwest::get(url)
+        .await
+        .with_context(|| format!("failed to request download archive '{}'", url))?;
+
+    let total_bytes = response.content_length().unwrap_or_default();
+    let pb = multi_progress.add(ProgressBar::new(total_bytes));
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{msg} [{bar:30}] {bytes}/{total_bytes}")
+            .progress_chars("=> "),
+    );
+    pb.set_message(format!("Album {}", album_id));
+
+    let file_name = url
+        .rsplit('/')
+        .next()
+        .and_then(|segment| segment.split('?').next())
+        .filter(|name| !name.is_empty())
+        .unwrap_or("download.zip");
+    let path = library.join(file_name);
+
+    let mut file = File::create(&path)
+        .await
+        .with_context(|| format!("unable to create file '{}'", path.display()))?;
+
+    while let Some(chunk) = response.chunk().await? {
+        file.write_all(&chunk).await?;
+        pb.inc(chunk.len() as u64);
+    }
+
+    pb.finish_and_clear();
+
+    Ok(path)
+}
+
+/// Extract `archive_path` (a Bandcamp download ZIP) into a directory under
+/// `library` named after the band and album, then remove the archive. If
+/// `tag_files` is set, fetch the album's track listing and write artist,
+/// album, date-added, and track tags into each extracted file via `lofty`.
+async fn extract_and_tag(
+    archive_path: &Path,
+    library: &Path,
+    album_id: u32,
+    meta: &AlbumMeta,
+    tag_files: bool,
+) -> Result<()> {
+    let destination = library.join(format!("{} - {}", meta.band_name, meta.album_title));
+    fs::create_dir_all(&destination)
+        .with_context(|| format!("unable to create '{}'", destination.display()))?;
+
+    let file = fs::File::open(archive_path)
+        .with_context(|| format!("unable to open archive '{}'", archive_path.display()))?;
+    let mut archive =
+        zip::ZipArchive::new(file).with_context(|| "downloaded archive is not a valid ZIP")?;
+
+    let mut extracted_paths = vec![];
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        let Some(name) = entry.enclosed_name().and_then(|p| p.file_name()) else {
+            continue;
+        };
+
+        let out_path = destination.join(name);
+        let mut out_file = fs::File::create(&out_path)
+            .with_context(|| format!("unable to create '{}'", out_path.display()))?;
+        std::io::copy(&mut entry, &mut out_file)?;
+
+        extracted_paths.push(out_path);
+    }
+
+    fs::remove_file(archive_path)
+        .with_context(|| format!("unable to remove archive '{}'", archive_path.display()))?;
+
+    if tag_files {
+        // Bandcamp download ZIPs commonly bundle non-audio extras (cover
+        // art, liner notes) alongside the tracks; only audio files line up
+        // with the track listing, so anything else must be excluded before
+        // matching files to tracks positionally.
+        let mut track_paths: Vec<_> = extracted_paths
+            .iter()
+            .filter(|path| is_audio_file(path.as_path()))
+            .collect();
+        track_paths.sort();
+
+        // Match extracted files, in sorted path order, to track metadata
+        // positionally, since Bandcamp's track order and the sorted file
+        // names agree in practice.
+        let tracks = track_listing(album_id).await.unwrap_or_default();
+        for (path, track) in track_paths.into_iter().zip(
+            tracks
+                .iter()
+                .map(Some)
+                .chain(std::iter::repeat(None)),
+        ) {
+            if let Err(err) = tag::tag_track(path, &meta.band_name, &meta.album_title, meta.added, track) {
+                log::warn!("unable to tag '{}': {:#}", path.display(), err);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if `path`'s extension looks like an audio file Bandcamp
+/// could have delivered, as opposed to a bundled extra like cover art or
+/// liner notes.
+fn is_audio_file(path: &Path) -> bool {
+    const AUDIO_EXTENSIONS: &[&str] = &[
+        "mp3", "flac", "aac", "ogg", "m4a", "alac", "wav", "aiff", "aif",
+    ];
+
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| AUDIO_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
@@ -25,3 +25,81 @@ pub enum Format {
     Wav,
     Aiff,
 }
+
+impl Format {
+    /// Returns the ordered chain of formats to try when downloading a
+    /// release requested as `self`. Bandcamp does not guarantee every
+    /// release offers every format, so callers should walk the chain in
+    /// order and download the first format the release actually has
+    /// available.
+    pub fn fallback_chain(self) -> Vec<Format> {
+        use Format::*;
+
+        match self {
+            Flac => vec![Flac, Mp3V0, Mp3],
+            Alac => vec![Alac, Flac, Mp3V0, Mp3],
+            Wav => vec![Wav, Flac, Mp3V0, Mp3],
+            Aiff => vec![Aiff, Flac, Mp3V0, Mp3],
+            Aac => vec![Aac, Mp3V0, Mp3],
+            OggVorbis => vec![OggVorbis, Mp3V0, Mp3],
+            Mp3V0 => vec![Mp3V0, Mp3],
+            Mp3 => vec![Mp3],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chain_always_starts_with_self_and_ends_with_mp3() {
+        for format in [
+            Format::Mp3V0,
+            Format::Mp3,
+            Format::Flac,
+            Format::Aac,
+            Format::OggVorbis,
+            Format::Alac,
+            Format::Wav,
+            Format::Aiff,
+        ] {
+            let chain = format.fallback_chain();
+            assert_eq!(chain.first(), Some(&format));
+            assert_eq!(chain.last(), Some(&Format::Mp3));
+        }
+    }
+
+    #[test]
+    fn lossless_formats_fall_back_through_flac_before_mp3() {
+        assert_eq!(
+            Format::Alac.fallback_chain(),
+            vec![Format::Alac, Format::Flac, Format::Mp3V0, Format::Mp3]
+        );
+        assert_eq!(
+            Format::Wav.fallback_chain(),
+            vec![Format::Wav, Format::Flac, Format::Mp3V0, Format::Mp3]
+        );
+        assert_eq!(
+            Format::Aiff.fallback_chain(),
+            vec![Format::Aiff, Format::Flac, Format::Mp3V0, Format::Mp3]
+        );
+    }
+
+    #[test]
+    fn lossy_formats_skip_straight_to_mp3() {
+        assert_eq!(
+            Format::Aac.fallback_chain(),
+            vec![Format::Aac, Format::Mp3V0, Format::Mp3]
+        );
+        assert_eq!(
+            Format::OggVorbis.fallback_chain(),
+            vec![Format::OggVorbis, Format::Mp3V0, Format::Mp3]
+        );
+    }
+
+    #[test]
+    fn mp3_chain_has_no_fallback() {
+        assert_eq!(Format::Mp3.fallback_chain(), vec![Format::Mp3]);
+    }
+}
@@ -16,11 +16,17 @@ use crate::{
     client::{Collection, List, Wishlist},
     config::Config,
     format::Format,
+    manifest::{Manifest, ManifestEntry},
 };
 
+mod browse;
 mod client;
 mod config;
+mod download;
 mod format;
+mod manifest;
+mod resolve;
+mod tag;
 
 // ---------------------------------------------------------------------------
 // Command-Line Application
@@ -43,6 +49,8 @@ enum Subcommand {
     Download(DownloadOpts),
     /// Synchronize a directory with a collection
     Sync(SyncOpts),
+    /// Interactively browse a collection or wishlist and select albums to download
+    Browse(BrowseOpts),
 }
 
 #[derive(Debug, Parser)]
@@ -59,6 +67,9 @@ struct ConfigureOpts {
     /// Default audio file format to download
     #[clap(long, short, possible_values = Format::VARIANTS)]
     default_format: Option<Format>,
+    /// Don't embed artist/album/track tags into downloaded files by default
+    #[clap(long, takes_value = false)]
+    no_tag_files: bool,
     /// Overwrite existing values with the provided values
     #[clap(long, short, takes_value = false)]
     update: bool,
@@ -75,16 +86,27 @@ struct ListOpts {
     /// List items from the wishlist instead
     #[clap(long, short, takes_value = false)]
     wishlist: bool,
+    /// Bypass the cache and re-fetch the listing from Bandcamp
+    #[clap(long, short, takes_value = false)]
+    refresh: bool,
 }
 
 #[derive(Debug, Parser)]
 struct DownloadOpts {
-    /// File format to download albums in
+    /// File format to download albums in; if Bandcamp does not offer this
+    /// format for a given release, progressively lower-fidelity formats
+    /// will be tried instead
     #[clap(long, short, possible_values = Format::VARIANTS)]
     format: Option<Format>,
-    /// One or more album IDs to download
+    /// Number of albums to download concurrently
+    #[clap(long, short, default_value_t = download::DEFAULT_JOBS)]
+    jobs: usize,
+    /// Don't embed artist/album/track tags into the downloaded files
+    #[clap(long, takes_value = false)]
+    no_tag: bool,
+    /// One or more album IDs or Bandcamp album/track URLs to download
     #[clap(required = true)]
-    album_ids: Vec<u32>,
+    album_ids: Vec<String>,
 }
 
 #[derive(Debug, Parser)]
@@ -92,11 +114,24 @@ struct SyncOpts {
     /// File format to download albums in
     #[clap(long, short, possible_values = Format::VARIANTS)]
     format: Option<Format>,
+    /// Number of albums to download concurrently
+    #[clap(long, short, default_value_t = download::DEFAULT_JOBS)]
+    jobs: usize,
+    /// Don't embed artist/album/track tags into the downloaded files
+    #[clap(long, takes_value = false)]
+    no_tag: bool,
     /// Directory to sync albums to
     #[clap(required = true)]
     directory: Option<PathBuf>,
 }
 
+#[derive(Debug, Parser)]
+struct BrowseOpts {
+    /// File format to download selected albums in
+    #[clap(long, short, possible_values = Format::VARIANTS)]
+    format: Option<Format>,
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     use Subcommand::*;
@@ -124,6 +159,7 @@ async fn main() -> Result<()> {
         List(opts) => list(config, opts).await,
         Download(opts) => download(config, opts).await,
         Sync(opts) => sync(config, opts).await,
+        Browse(opts) => browse(config, opts).await,
     }
 }
 
@@ -202,7 +238,8 @@ fn configure_create(opts: ConfigureOpts) -> Result<()> {
 
     // Create and save the configuration to the config file location at
     // '~/.camper/config.toml'.
-    let config = Config::new(fan_id, identity, library, format);
+    let mut config = Config::new(fan_id, identity, library, format);
+    config.tag_files = Some(!opts.no_tag_files);
     config.save()?;
 
     Ok(())
@@ -233,6 +270,10 @@ fn configure_update(config: Config, opts: ConfigureOpts) -> Result<()> {
         messages.push(format!("Updated default format to {}\n", format));
         config.format = Some(format);
     }
+    if opts.no_tag_files {
+        messages.push("Disabled tagging of downloaded files\n".to_owned());
+        config.tag_files = Some(false);
+    }
 
     config.save()?;
     for message in messages {
@@ -246,15 +287,16 @@ async fn list(config: Config, opts: ListOpts) -> Result<()> {
     // A fan ID can optionally be provided to list their collection(s) instead. By
     // default, the configured fan ID will be used.
     let fan_id = opts.fan_id.or(config.fan_id).unwrap();
+    let ttl = config.cache_ttl();
     let identity = config.identity.unwrap();
 
     // Query all items from the specified collection. We make authenticated requests
     // here to show any private or hidden items when listing the authenticated users
     // collection(s).
     let items = if opts.wishlist {
-        Wishlist::list(fan_id, &identity).await?
+        Wishlist::list(fan_id, &identity, ttl, opts.refresh).await?
     } else {
-        Collection::list(fan_id, &identity).await?
+        Collection::list(fan_id, &identity, ttl, opts.refresh).await?
     };
     let total_items = items.len();
 
@@ -285,11 +327,174 @@ async fn list(config: Config, opts: ListOpts) -> Result<()> {
     Ok(())
 }
 
-async fn download(_config: Config, _opts: DownloadOpts) -> Result<()> {
+async fn download(config: Config, opts: DownloadOpts) -> Result<()> {
+    let fan_id = config.fan_id.unwrap();
+    let identity = config.identity.unwrap();
+    let library = config.library.unwrap();
+
+    // Fall back to the user's configured default format if one wasn't given
+    // for this particular invocation, then expand it into the ordered chain
+    // of formats to try.
+    let format = opts.format.or(config.format).unwrap();
+    let formats = format.fallback_chain();
+    let tag_files = !opts.no_tag && config.tag_files.unwrap_or(true);
+
+    // Each argument may be a raw album ID or a Bandcamp album/track URL;
+    // resolve them all to album IDs up front.
+    let mut album_ids = Vec::with_capacity(opts.album_ids.len());
+    for input in &opts.album_ids {
+        album_ids.push(resolve::resolve_album_id(input).await?);
+    }
+
+    download::download_albums(
+        fan_id,
+        &identity,
+        album_ids,
+        &formats,
+        &library,
+        opts.jobs,
+        tag_files,
+    )
+    .await
+}
+
+async fn sync(config: Config, opts: SyncOpts) -> Result<()> {
+    use std::sync::Arc;
+
+    use indicatif::MultiProgress;
+    use tokio::sync::{Mutex, Semaphore};
+
+    let fan_id = config.fan_id.unwrap();
+    let ttl = config.cache_ttl();
+    let identity = Arc::new(config.identity.unwrap());
+    let directory = Arc::new(opts.directory.unwrap());
+
+    let format = opts.format.or(config.format).unwrap();
+    let formats = Arc::new(format.fallback_chain());
+    let tag_files = !opts.no_tag && config.tag_files.unwrap_or(true);
+
+    // Diff the current collection against the manifest so that albums which
+    // were already downloaded by a previous `sync` run aren't fetched again.
+    let items = Collection::list(fan_id, &identity, ttl, false).await?;
+    let manifest = Manifest::load(&directory)?;
+    let manifest = Arc::new(Mutex::new(manifest));
+
+    let pending: Vec<_> = {
+        let manifest = manifest.lock().await;
+        items
+            .into_iter()
+            .filter(|item| !manifest.is_complete(item.album_id))
+            .collect()
+    };
+
+    download::validate_jobs(opts.jobs)?;
+    let semaphore = Arc::new(Semaphore::new(opts.jobs));
+    let multi_progress = Arc::new(MultiProgress::new());
+
+    let tasks = pending.into_iter().map(|item| {
+        let semaphore = Arc::clone(&semaphore);
+        let multi_progress = Arc::clone(&multi_progress);
+        let identity = Arc::clone(&identity);
+        let formats = Arc::clone(&formats);
+        let directory = Arc::clone(&directory);
+        let manifest = Arc::clone(&manifest);
+
+        tokio::spawn(async move {
+            let _permit = semaphore.acquire().await.unwrap();
+
+            log::info!("syncing '{}' by {}", item.album_title, item.band_name);
+            let meta = download::AlbumMeta {
+                band_name: item.band_name.clone(),
+                album_title: item.album_title.clone(),
+                added: item.added,
+            };
+            let downloaded_format = download::download_album(
+                fan_id,
+                &identity,
+                item.album_id,
+                &meta,
+                &formats,
+                &directory,
+                tag_files,
+                &multi_progress,
+            )
+            .await?;
+
+            manifest.lock().await.complete(
+                item.album_id,
+                ManifestEntry {
+                    band_name: item.band_name,
+                    album_title: item.album_title,
+                    format: downloaded_format,
+                    completed: true,
+                },
+            )
+        })
+    });
+
+    for task in tasks {
+        task.await??;
+    }
+
     Ok(())
 }
 
-async fn sync(_config: Config, _opts: SyncOpts) -> Result<()> {
+async fn browse(config: Config, opts: BrowseOpts) -> Result<()> {
+    use std::sync::Arc;
+
+    use indicatif::MultiProgress;
+    use tokio::sync::Semaphore;
+
+    let fan_id = config.fan_id.unwrap();
+    let ttl = config.cache_ttl();
+    let format = opts.format.or(config.format).unwrap();
+    let tag_files = config.tag_files.unwrap_or(true);
+    let identity = Arc::new(config.identity.unwrap());
+    let library = Arc::new(config.library.unwrap());
+
+    let items = browse::run(fan_id, &identity, ttl).await?;
+    if items.is_empty() {
+        return Ok(());
+    }
+
+    let formats = Arc::new(format.fallback_chain());
+    let semaphore = Arc::new(Semaphore::new(download::DEFAULT_JOBS));
+    let multi_progress = Arc::new(MultiProgress::new());
+
+    let tasks = items.into_iter().map(|item| {
+        let semaphore = Arc::clone(&semaphore);
+        let multi_progress = Arc::clone(&multi_progress);
+        let identity = Arc::clone(&identity);
+        let formats = Arc::clone(&formats);
+        let library = Arc::clone(&library);
+
+        tokio::spawn(async move {
+            let _permit = semaphore.acquire().await.unwrap();
+
+            log::info!("downloading '{}' by {}", item.album_title, item.band_name);
+            let meta = download::AlbumMeta {
+                band_name: item.band_name,
+                album_title: item.album_title,
+                added: item.added,
+            };
+            download::download_album(
+                fan_id,
+                &identity,
+                item.album_id,
+                &meta,
+                &formats,
+                &library,
+                tag_files,
+                &multi_progress,
+            )
+            .await
+        })
+    });
+
+    for task in tasks {
+        task.await??;
+    }
+
     Ok(())
 }
 
@@ -0,0 +1,140 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::format::Format;
+
+// ---------------------------------------------------------------------------
+// Manifest Entry
+
+/// A record of a single album's sync status, persisted alongside the synced
+/// files so that an interrupted `sync` can resume without re-downloading
+/// albums that already completed.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ManifestEntry {
+    pub band_name: String,
+    pub album_title: String,
+    pub format: Format,
+    pub completed: bool,
+}
+
+// ---------------------------------------------------------------------------
+// Manifest
+
+/// Tracks, per `album_id`, which albums in a collection have already been
+/// synced to a local directory. This mirrors the manifest file approach
+/// used by comparable collection managers, giving `sync` a `git pull`-style
+/// "download everything I don't already have" workflow.
+#[derive(Debug, Default)]
+pub struct Manifest {
+    path: PathBuf,
+    entries: HashMap<u32, ManifestEntry>,
+}
+
+impl Manifest {
+    const FILE_NAME: &'static str = "manifest.json";
+
+    /// Load the manifest from `directory`, or return an empty manifest if
+    /// one does not exist yet.
+    pub fn load(directory: &Path) -> Result<Self> {
+        let path = directory.join(Self::FILE_NAME);
+        let entries = match fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text)
+                .with_context(|| format!("unable to parse manifest '{}'", path.display()))?,
+            _ => HashMap::new(),
+        };
+
+        Ok(Self { path, entries })
+    }
+
+    /// Returns `true` if `album_id` has already been fully downloaded
+    /// according to this manifest.
+    pub fn is_complete(&self, album_id: u32) -> bool {
+        matches!(self.entries.get(&album_id), Some(entry) if entry.completed)
+    }
+
+    /// Record `entry` for `album_id` and persist the manifest to disk. The
+    /// album counts as complete only if `entry.completed` is set.
+    pub fn complete(&mut self, album_id: u32, entry: ManifestEntry) -> Result<()> {
+        self.entries.insert(album_id, entry);
+        self.save()
+    }
+
+    /// Write the manifest to a temporary file and rename it into place, so
+    /// that a crash mid-write can't leave behind a corrupt manifest.
+    fn save(&self) -> Result<()> {
+        let tmp_path = self.path.with_extension("json.tmp");
+        let json = serde_json::to_string_pretty(&self.entries)?;
+
+        fs::write(&tmp_path, json)
+            .with_context(|| format!("unable to write manifest '{}'", tmp_path.display()))?;
+        fs::rename(&tmp_path, &self.path)
+            .with_context(|| format!("unable to save manifest '{}'", self.path.display()))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A scratch directory under the system temp dir, unique to `label`,
+    /// removed on drop so tests don't leak files into `$TMPDIR`.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(label: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("camper-manifest-test-{}", label));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn entry(completed: bool) -> ManifestEntry {
+        ManifestEntry {
+            band_name: "Some Band".to_owned(),
+            album_title: "Some Album".to_owned(),
+            format: Format::Flac,
+            completed,
+        }
+    }
+
+    #[test]
+    fn unknown_album_is_not_complete() {
+        let dir = TempDir::new("unknown");
+        let manifest = Manifest::load(&dir.0).unwrap();
+        assert!(!manifest.is_complete(1));
+    }
+
+    #[test]
+    fn completing_an_album_marks_it_complete_and_persists() {
+        let dir = TempDir::new("complete");
+        let mut manifest = Manifest::load(&dir.0).unwrap();
+        manifest.complete(1, entry(true)).unwrap();
+        assert!(manifest.is_complete(1));
+
+        let reloaded = Manifest::load(&dir.0).unwrap();
+        assert!(reloaded.is_complete(1));
+    }
+
+    #[test]
+    fn an_entry_recorded_as_incomplete_is_not_complete() {
+        let dir = TempDir::new("incomplete");
+        let mut manifest = Manifest::load(&dir.0).unwrap();
+        manifest.complete(1, entry(false)).unwrap();
+        assert!(!manifest.is_complete(1));
+    }
+}
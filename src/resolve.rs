@@ -0,0 +1,124 @@
+use anyhow::{anyhow, bail, Context, Result};
+use url::Url;
+
+/// Bandcamp hosts we trust to resolve album/track URLs against. Artist
+/// subdomains (`<artist>.bandcamp.com`) are matched by suffix below.
+const ALLOWED_HOSTS: &[&str] = &["bandcamp.com"];
+
+/// Resolve a single `camper download` argument, which may be a raw numeric
+/// album ID or a Bandcamp album/track URL, into an `album_id`.
+pub async fn resolve_album_id(input: &str) -> Result<u32> {
+    if let Ok(album_id) = input.parse() {
+        return Ok(album_id);
+    }
+
+    resolve_url(input).await
+}
+
+/// Fetch `input` as a Bandcamp page and pull the `album_id` out of its
+/// embedded track/album metadata.
+async fn resolve_url(input: &str) -> Result<u32> {
+    let url =
+        Url::parse(input).with_context(|| format!("'{}' is not a valid album ID or URL", input))?;
+
+    let host = url.host_str().unwrap_or_default();
+    if !is_allowed_host(host) {
+        bail!(
+            "unsupported host '{}'; only Bandcamp album/track URLs are supported",
+            host
+        );
+    }
+
+    let body = reqwest::get(url.clone())
+        .await
+        .with_context(|| format!("failed to fetch '{}'", url))?
+        .text()
+        .await
+        .with_context(|| format!("failed to read response body for '{}'", url))?;
+
+    extract_album_id(&body).with_context(|| format!("unable to determine album ID for '{}'", url))
+}
+
+/// Returns `true` if `host` is `bandcamp.com` itself or an artist subdomain
+/// of it (e.g. `artist.bandcamp.com`).
+fn is_allowed_host(host: &str) -> bool {
+    ALLOWED_HOSTS
+        .iter()
+        .any(|allowed| host == *allowed || host.ends_with(&format!(".{}", allowed)))
+}
+
+/// Bandcamp embeds track/album metadata in a `data-tralbum` attribute on the
+/// page as an HTML-escaped JSON blob; pull the album ID out of it.
+///
+/// On an album page, `current.id` already *is* the album ID. On a track
+/// page, `current.id` is the *track's* ID and the parent album is instead
+/// named by the top-level `album_id` field, so that field must be preferred
+/// whenever it's present; only standalone releases with no parent album
+/// (where `album_id` is absent or `null`) fall back to `current.id`.
+fn extract_album_id(body: &str) -> Result<u32> {
+    const MARKER: &str = "data-tralbum=\"";
+
+    let start = body
+        .find(MARKER)
+        .map(|i| i + MARKER.len())
+        .ok_or_else(|| anyhow!("page does not contain embedded Bandcamp metadata"))?;
+    let end = body[start..]
+        .find('"')
+        .map(|i| start + i)
+        .ok_or_else(|| anyhow!("malformed embedded Bandcamp metadata"))?;
+
+    let json = body[start..end].replace("&quot;", "\"").replace("&amp;", "&");
+    let value: serde_json::Value =
+        serde_json::from_str(&json).context("unable to parse embedded Bandcamp metadata")?;
+
+    value["album_id"]
+        .as_u64()
+        .or_else(|| value["current"]["id"].as_u64())
+        .map(|id| id as u32)
+        .ok_or_else(|| anyhow!("embedded metadata is missing an album id"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allows_bare_and_subdomain_bandcamp_hosts() {
+        assert!(is_allowed_host("bandcamp.com"));
+        assert!(is_allowed_host("artist.bandcamp.com"));
+    }
+
+    #[test]
+    fn rejects_unrelated_and_lookalike_hosts() {
+        assert!(!is_allowed_host("notbandcamp.com"));
+        assert!(!is_allowed_host("evilbandcamp.com"));
+        assert!(!is_allowed_host(""));
+    }
+
+    fn page(body: &str) -> String {
+        format!(r#"<div data-tralbum="{}"></div>"#, body)
+    }
+
+    #[test]
+    fn prefers_album_id_on_a_track_page() {
+        let body = page(r#"{&quot;album_id&quot;:123,&quot;current&quot;:{&quot;id&quot;:456}}"#);
+        assert_eq!(extract_album_id(&body).unwrap(), 123);
+    }
+
+    #[test]
+    fn falls_back_to_current_id_on_an_album_or_standalone_page() {
+        let body = page(r#"{&quot;current&quot;:{&quot;id&quot;:789}}"#);
+        assert_eq!(extract_album_id(&body).unwrap(), 789);
+    }
+
+    #[test]
+    fn errors_when_metadata_marker_is_missing() {
+        assert!(extract_album_id("<html></html>").is_err());
+    }
+
+    #[test]
+    fn errors_when_neither_id_is_present() {
+        let body = page(r#"{&quot;foo&quot;:1}"#);
+        assert!(extract_album_id(&body).is_err());
+    }
+}
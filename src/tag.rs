@@ -0,0 +1,45 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use lofty::{Accessor, ItemKey, Probe, Tag, TagExt, TaggedFileExt};
+
+use crate::client::download::TrackInfo;
+
+/// Embed artist, album, date-added, and track metadata into the audio file
+/// at `path`. Uses `lofty` so this works transparently regardless of which
+/// format Bandcamp actually delivered (FLAC, MP3, etc.).
+pub fn tag_track(
+    path: &Path,
+    band_name: &str,
+    album_title: &str,
+    added: DateTime<Utc>,
+    track: Option<&TrackInfo>,
+) -> Result<()> {
+    let mut tagged_file = Probe::open(path)
+        .with_context(|| format!("unable to open '{}' for tagging", path.display()))?
+        .read()
+        .with_context(|| format!("unable to read tag data from '{}'", path.display()))?;
+
+    let tag = match tagged_file.primary_tag_mut() {
+        Some(tag) => tag,
+        None => {
+            tagged_file.insert_tag(Tag::new(tagged_file.primary_tag_type()));
+            tagged_file.primary_tag_mut().unwrap()
+        }
+    };
+
+    tag.set_artist(band_name.to_owned());
+    tag.set_album(album_title.to_owned());
+    tag.insert_text(ItemKey::RecordingDate, added.to_rfc3339());
+
+    if let Some(track) = track {
+        tag.set_track(track.track_number);
+        tag.set_title(track.title.clone());
+    }
+
+    tag.save_to_path(path)
+        .with_context(|| format!("unable to save tags to '{}'", path.display()))?;
+
+    Ok(())
+}